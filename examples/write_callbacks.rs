@@ -0,0 +1,42 @@
+//! Demonstrates a library user supplying a `WriteCallbacks` implementation
+//! via `Config::write_callbacks`, rather than `cdecl`'s internal call graph
+//! being the only thing that can ever construct one.
+
+use cbindgen::{
+    generate_header, Config, Function, FunctionArgument, FunctionPath, PrimitiveType, Type,
+    WriteCallbacks,
+};
+
+struct ScreamingCallbacks;
+
+impl WriteCallbacks for ScreamingCallbacks {
+    fn ident(&self, proposed: &str) -> Option<String> {
+        Some(proposed.to_uppercase())
+    }
+}
+
+fn main() {
+    let config = Config {
+        write_callbacks: Some(Box::new(ScreamingCallbacks)),
+        ..Config::default()
+    };
+
+    let greet = Function::new(
+        FunctionPath::new("greet".to_owned()),
+        vec![FunctionArgument {
+            name: Some("name".to_owned()),
+            ty: Type::Ptr {
+                ty: Box::new(Type::Primitive(PrimitiveType::Char)),
+                is_const: true,
+                is_nullable: false,
+                is_ref: false,
+                is_restrict: false,
+            },
+        }],
+        Type::Primitive(PrimitiveType::Void),
+    );
+
+    let mut out = Vec::new();
+    generate_header(&mut out, &[greet], &config);
+    print!("{}", String::from_utf8(out).unwrap());
+}
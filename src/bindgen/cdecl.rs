@@ -13,13 +13,94 @@ use crate::bindgen::{Config, Language};
 // See Section 6.7, Declarations, in the C standard for background.
 // http://www.open-std.org/jtc1/sc22/wg14/www/docs/n1570.pdf
 
+/// Lets a library user intercept and rewrite the pieces of a C declaration
+/// as it's written out, without forking `cdecl.rs`. This mirrors bindgen's
+/// `ParseCallbacks`, but hooks into cbindgen's declaration writer instead of
+/// its Rust-side parser: a user can rename an emitted identifier, override a
+/// type's rendered name, or inject an attribute (e.g.
+/// `__attribute__((deprecated))`, a SAL annotation, a custom alignment
+/// macro) just before an identifier.
+///
+/// All methods default to returning `None`, meaning "use cbindgen's normal
+/// output", so an implementor only needs to override the hooks it cares
+/// about.
+pub trait WriteCallbacks {
+    /// Called with the type-specifier cbindgen is about to write (e.g.
+    /// `"MyStruct"`); return `Some` to replace it.
+    fn type_name(&self, _proposed: &str) -> Option<String> {
+        None
+    }
+
+    /// Called with the type-qualifier cbindgen is about to write (e.g.
+    /// `"const"`); return `Some` to replace it.
+    fn type_qualifiers(&self, _proposed: &str) -> Option<String> {
+        None
+    }
+
+    /// Called with each identifier cbindgen is about to write (a function,
+    /// field or parameter name); return `Some` to rename it.
+    fn ident(&self, _proposed: &str) -> Option<String> {
+        None
+    }
+
+    /// Called just before the identifier is written; return `Some` to
+    /// inject an attribute (or any other text) right before the identifier,
+    /// after any `*` or `(` of the declarator.
+    fn pre_ident_attribute(&self, _proposed: &str) -> Option<String> {
+        None
+    }
+}
+
+/// A pointer's nullability, as tracked through from the Rust side. Used both
+/// for the existing `non_null_attribute` config and, when
+/// `config.pointer.clang_nullability` is enabled, to emit Clang's
+/// `_Nonnull`/`_Nullable`/`_Null_unspecified` type qualifiers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Nullability {
+    NonNull,
+    Nullable,
+    /// We don't have Rust-side information to say either way (e.g. the
+    /// synthetic pointer cbindgen wraps a `Type::FuncPtr` in).
+    Unspecified,
+}
+
+impl Nullability {
+    fn from_is_nullable(is_nullable: bool) -> Nullability {
+        if is_nullable {
+            Nullability::Nullable
+        } else {
+            Nullability::NonNull
+        }
+    }
+
+    fn is_nullable(self) -> bool {
+        self != Nullability::NonNull
+    }
+
+    fn clang_qualifier(self) -> &'static str {
+        match self {
+            Nullability::NonNull => "_Nonnull",
+            Nullability::Nullable => "_Nullable",
+            Nullability::Unspecified => "_Null_unspecified",
+        }
+    }
+}
+
 enum CDeclarator {
     Ptr {
         is_const: bool,
-        is_nullable: bool,
+        nullability: Nullability,
         is_ref: bool,
+        /// C99 `restrict` (N1570 §6.7.3), written `restrict`/`__restrict`
+        /// depending on language, right after the `*`.
+        is_restrict: bool,
+    },
+    Array {
+        len: String,
+        /// Whether to emit the C99 `[static N]` array-parameter hint
+        /// (N1570 §6.7.6.3p7) instead of plain `[N]`.
+        is_static: bool,
     },
-    Array(String),
     Func(Vec<(Option<String>, CDecl)>, bool),
 }
 
@@ -38,6 +119,11 @@ struct CDecl {
     type_generic_args: Vec<Type>,
     declarators: Vec<CDeclarator>,
     type_ctype: Option<DeclarationType>,
+    /// Whether this `CDecl` is being built directly for a function
+    /// parameter, as opposed to e.g. a struct field or a return type. Only
+    /// relevant to the top-level declarator, since `[static N]` only makes
+    /// sense for an array that is itself the parameter's type.
+    is_param: bool,
 }
 
 impl CDecl {
@@ -48,6 +134,7 @@ impl CDecl {
             type_generic_args: Vec::new(),
             declarators: Vec::new(),
             type_ctype: None,
+            is_param: false,
         }
     }
 
@@ -56,6 +143,14 @@ impl CDecl {
         cdecl.build_type(t, false);
         cdecl
     }
+
+    fn from_param_type(t: &Type) -> CDecl {
+        let mut cdecl = CDecl::new();
+        cdecl.is_param = true;
+        cdecl.build_type(t, false);
+        cdecl
+    }
+
     fn from_func(f: &Function, layout_vertical: bool) -> CDecl {
         let mut cdecl = CDecl::new();
         cdecl.build_func(f, layout_vertical);
@@ -66,7 +161,7 @@ impl CDecl {
         let args = f
             .args
             .iter()
-            .map(|arg| (arg.name.clone(), CDecl::from_type(&arg.ty)))
+            .map(|arg| (arg.name.clone(), CDecl::from_param_type(&arg.ty)))
             .collect();
         self.declarators
             .push(CDeclarator::Func(args, layout_vertical));
@@ -96,7 +191,7 @@ impl CDecl {
                     "error generating cdecl for {:?}",
                     t
                 );
-                self.type_generic_args = generic.generics().to_owned();
+                self.type_generic_args = generic.generics().to_vec();
                 self.type_ctype = generic.ctype().cloned();
             }
             Type::Primitive(ref p) => {
@@ -121,17 +216,27 @@ impl CDecl {
                 is_nullable,
                 is_const: ptr_is_const,
                 is_ref,
+                is_restrict,
             } => {
                 self.declarators.push(CDeclarator::Ptr {
                     is_const,
-                    is_nullable: *is_nullable,
+                    nullability: Nullability::from_is_nullable(*is_nullable),
                     is_ref: *is_ref,
+                    is_restrict: *is_restrict,
                 });
                 self.build_type(ty, *ptr_is_const);
             }
-            Type::Array(ref t, ref constant) => {
+            Type::Array(ref t, ref constant, is_nullable) => {
                 let len = constant.as_str().to_owned();
-                self.declarators.push(CDeclarator::Array(len));
+                // `[static N]` only documents a real contract when we're the
+                // parameter's own array syntax (not nested under a pointer
+                // elsewhere), the bound is a known non-zero constant, and the
+                // pointer it decays to is guaranteed non-null.
+                let is_static = self.is_param
+                    && self.declarators.is_empty()
+                    && !*is_nullable
+                    && len.parse::<u64>().is_ok_and(|n| n != 0);
+                self.declarators.push(CDeclarator::Array { len, is_static });
                 self.build_type(t, is_const);
             }
             Type::FuncPtr(ref ret, ref args) => {
@@ -141,8 +246,9 @@ impl CDecl {
                     .collect();
                 self.declarators.push(CDeclarator::Ptr {
                     is_const: false,
-                    is_nullable: true,
+                    nullability: Nullability::Unspecified,
                     is_ref: false,
+                    is_restrict: false,
                 });
                 self.declarators.push(CDeclarator::Func(args, false));
                 self.build_type(ret, false);
@@ -150,26 +256,45 @@ impl CDecl {
         }
     }
 
-    fn write<F: Write>(&self, out: &mut SourceWriter<F>, ident: Option<&str>, config: &Config) {
+    fn write<F: Write>(
+        &self,
+        out: &mut SourceWriter<F>,
+        ident: Option<&str>,
+        config: &Config,
+        callbacks: Option<&dyn WriteCallbacks>,
+    ) {
         // Write the type-specifier and type-qualifier first
         if !self.type_qualifers.is_empty() {
-            write!(out, "{} ", self.type_qualifers);
+            let type_qualifers = callbacks
+                .and_then(|cb| cb.type_qualifiers(&self.type_qualifers))
+                .unwrap_or_else(|| self.type_qualifers.clone());
+            write!(out, "{} ", type_qualifers);
         }
 
         if let Some(ref ctype) = self.type_ctype {
             write!(out, "{} ", ctype.to_str());
         }
 
-        write!(out, "{}", self.type_name);
+        let type_name = callbacks
+            .and_then(|cb| cb.type_name(&self.type_name))
+            .unwrap_or_else(|| self.type_name.clone());
+        write!(out, "{}", type_name);
 
         if !self.type_generic_args.is_empty() {
             out.write("<");
-            out.write_horizontal_source_list(&self.type_generic_args, ListType::Join(", "));
+            out.write_horizontal_source_list(
+                &self.type_generic_args,
+                ListType::Join(", "),
+                config,
+                callbacks,
+            );
             out.write(">");
         }
 
-        // When we have an identifier, put a space between the type and the declarators
-        if ident.is_some() {
+        // Put a space between the type and the declarators, unless there are
+        // no declarators and no identifier (a bare type, e.g. a generic
+        // argument) to avoid a trailing space.
+        if ident.is_some() || !self.declarators.is_empty() {
             out.write(" ");
         }
 
@@ -183,20 +308,33 @@ impl CDecl {
             match *declarator {
                 CDeclarator::Ptr {
                     is_const,
-                    is_nullable,
+                    nullability,
                     is_ref,
+                    is_restrict,
                 } => {
                     out.write(if is_ref { "&" } else { "*" });
                     if is_const {
                         out.write("const ");
                     }
-                    if !is_nullable && !is_ref {
-                        if let Some(attr) = &config.pointer.non_null_attribute {
-                            write!(out, "{} ", attr);
+                    if is_restrict && !is_ref {
+                        let keyword = if config.language == Language::C {
+                            "restrict"
+                        } else {
+                            "__restrict"
+                        };
+                        write!(out, "{} ", keyword);
+                    }
+                    if !is_ref {
+                        if config.pointer.clang_nullability {
+                            write!(out, "{} ", nullability.clang_qualifier());
+                        } else if !nullability.is_nullable() {
+                            if let Some(attr) = &config.pointer.non_null_attribute {
+                                write!(out, "{} ", attr);
+                            }
                         }
                     }
                 }
-                CDeclarator::Array(..) => {
+                CDeclarator::Array { .. } => {
                     if next_is_pointer {
                         out.write("(");
                     }
@@ -211,6 +349,13 @@ impl CDecl {
 
         // Write the identifier
         if let Some(ident) = ident {
+            if let Some(attr) = callbacks.and_then(|cb| cb.pre_ident_attribute(ident)) {
+                write!(out, "{} ", attr);
+            }
+
+            let ident = callbacks
+                .and_then(|cb| cb.ident(ident))
+                .unwrap_or_else(|| ident.to_owned());
             write!(out, "{}", ident);
         }
 
@@ -224,11 +369,18 @@ impl CDecl {
                 CDeclarator::Ptr { .. } => {
                     last_was_pointer = true;
                 }
-                CDeclarator::Array(ref constant) => {
+                CDeclarator::Array {
+                    ref len,
+                    is_static,
+                } => {
                     if last_was_pointer {
                         out.write(")");
                     }
-                    write!(out, "[{}]", constant);
+                    if is_static {
+                        write!(out, "[static {}]", len);
+                    } else {
+                        write!(out, "[{}]", len);
+                    }
 
                     last_was_pointer = false;
                 }
@@ -253,7 +405,7 @@ impl CDecl {
                             // Convert &Option<String> to Option<&str>
                             let arg_ident = arg_ident.as_ref().map(|x| x.as_ref());
 
-                            arg_ty.write(out, arg_ident, config);
+                            arg_ty.write(out, arg_ident, config, callbacks);
                         }
                         out.pop_tab();
                     } else {
@@ -265,7 +417,7 @@ impl CDecl {
                             // Convert &Option<String> to Option<&str>
                             let arg_ident = arg_ident.as_ref().map(|x| x.as_ref());
 
-                            arg_ty.write(out, arg_ident, config);
+                            arg_ty.write(out, arg_ident, config, callbacks);
                         }
                     }
                     out.write(")");
@@ -282,14 +434,26 @@ pub fn write_func<F: Write>(
     f: &Function,
     layout_vertical: bool,
     config: &Config,
+    callbacks: Option<&dyn WriteCallbacks>,
 ) {
-    CDecl::from_func(f, layout_vertical).write(out, Some(f.path().name()), config);
+    CDecl::from_func(f, layout_vertical).write(out, Some(f.path().name()), config, callbacks);
 }
 
-pub fn write_field<F: Write>(out: &mut SourceWriter<F>, t: &Type, ident: &str, config: &Config) {
-    CDecl::from_type(t).write(out, Some(ident), config);
+pub fn write_field<F: Write>(
+    out: &mut SourceWriter<F>,
+    t: &Type,
+    ident: &str,
+    config: &Config,
+    callbacks: Option<&dyn WriteCallbacks>,
+) {
+    CDecl::from_type(t).write(out, Some(ident), config, callbacks);
 }
 
-pub fn write_type<F: Write>(out: &mut SourceWriter<F>, t: &Type, config: &Config) {
-    CDecl::from_type(t).write(out, None, config);
+pub fn write_type<F: Write>(
+    out: &mut SourceWriter<F>,
+    t: &Type,
+    config: &Config,
+    callbacks: Option<&dyn WriteCallbacks>,
+) {
+    CDecl::from_type(t).write(out, None, config, callbacks);
 }
@@ -0,0 +1,70 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::bindgen::cdecl::WriteCallbacks;
+use crate::bindgen::postprocessing::PostProcessingPass;
+
+/// The C family to emit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    Cxx,
+    C,
+}
+
+/// Which of the style variants (`enum class`, tagged union, or both) to
+/// emit for Rust enums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    Both,
+    Tag,
+    Type,
+}
+
+/// Settings controlling how pointers are rendered.
+#[derive(Default)]
+pub struct PtrConfig {
+    /// An attribute (e.g. `_Nonnull`, a custom macro) to place after `*`
+    /// when a pointer is known to be non-null.
+    pub non_null_attribute: Option<String>,
+    /// `[pointer] clang_nullability = true`: render Clang's
+    /// `_Nonnull`/`_Nullable`/`_Null_unspecified` type qualifiers after `*`
+    /// instead of `non_null_attribute`, so headers are clean under
+    /// `-Wnullability-completeness`.
+    pub clang_nullability: bool,
+}
+
+/// `[export.dynamic_loading]`: emit a struct of function pointers plus a
+/// `dlsym`-based loader instead of plain `extern` prototypes, so a C/C++
+/// consumer can late-bind a Rust `cdylib` via `dlopen`.
+#[derive(Default)]
+pub struct DynamicLoadingConfig {
+    /// Name of the generated function-pointer-table struct.
+    pub struct_name: String,
+    /// Name of the generated `bool loader(Struct *out, void *handle)`
+    /// function.
+    pub loader_name: String,
+}
+
+/// `[export]` settings controlling the shape of the generated header.
+#[derive(Default)]
+pub struct ExportConfig {
+    pub dynamic_loading: Option<DynamicLoadingConfig>,
+    /// `[export.postprocess] passes = ["sort-declarations", ...]`: an
+    /// ordered list of text-level passes to run over the generated header.
+    pub postprocess: Vec<PostProcessingPass>,
+}
+
+#[derive(Default)]
+pub struct Config {
+    pub language: Language,
+    pub pointer: PtrConfig,
+    pub export: ExportConfig,
+    /// Lets a library user supply a `WriteCallbacks` implementation to
+    /// intercept type/ident rendering. Not part of the TOML config (there's
+    /// no sensible textual representation for a trait object); set the
+    /// field directly on the `Config` passed to `generate_header`, e.g.
+    /// `Config { write_callbacks: Some(Box::new(MyCallbacks)), ..Default::default() }`.
+    pub write_callbacks: Option<Box<dyn WriteCallbacks>>,
+}
@@ -0,0 +1,22 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+/// Whether a user type should be prefixed with `struct`/`union`/`enum` when
+/// referenced from C (as opposed to C++, where the tag name alone suffices).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeclarationType {
+    Struct,
+    Union,
+    Enum,
+}
+
+impl DeclarationType {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            DeclarationType::Struct => "struct",
+            DeclarationType::Union => "union",
+            DeclarationType::Enum => "enum",
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::io::Write;
+
+use crate::bindgen::cdecl::{self, WriteCallbacks};
+use crate::bindgen::ir::{Function, Type};
+use crate::bindgen::writer::SourceWriter;
+use crate::bindgen::Config;
+
+// Emits an opt-in "dynamic loading" variant of the header: instead of plain
+// `extern` prototypes, we emit a struct of function pointers together with a
+// loader that resolves each symbol via `dlsym` and assigns it into the
+// struct. This lets a C/C++ consumer late-bind a Rust `cdylib` via `dlopen`
+// rather than linking against it directly.
+
+/// Builds the `Type::FuncPtr` a function's entry in the API struct (or its
+/// `dlsym` cast) is rendered as. Argument names are dropped: a function
+/// pointer *type* doesn't carry them in C, unlike the `extern` prototype
+/// `write_func` renders for the static header.
+fn func_ptr_type(function: &Function) -> Type {
+    let args = function
+        .args
+        .iter()
+        .map(|arg| (None, arg.ty.clone()))
+        .collect();
+    Type::FuncPtr(Box::new(function.ret.clone()), args)
+}
+
+/// Writes `typedef struct struct_name { Ret (*name)(args); ... } struct_name;`, reusing
+/// the same `FuncPtr` rendering that `build_type` uses for Rust function
+/// pointers so the field types stay in sync with the static header.
+pub fn write_api_struct<F: Write>(
+    out: &mut SourceWriter<F>,
+    functions: &[Function],
+    struct_name: &str,
+    config: &Config,
+    callbacks: Option<&dyn WriteCallbacks>,
+) {
+    write!(out, "typedef struct {}", struct_name);
+    out.push_set_spaces(2);
+    out.open_brace();
+
+    for (i, function) in functions.iter().enumerate() {
+        if i != 0 {
+            out.new_line();
+        }
+
+        let func_ptr = func_ptr_type(function);
+
+        cdecl::write_field(out, &func_ptr, function.path().name(), config, callbacks);
+        out.write(";");
+    }
+
+    out.pop_tab();
+    out.new_line();
+    out.close_brace(false);
+    write!(out, " {};", struct_name);
+    out.new_line();
+}
+
+/// Writes `bool loader_name(struct_name *out, void *handle)`, which resolves
+/// every field of the API struct via `dlsym` and fails (returning `false`)
+/// as soon as one symbol can't be found.
+pub fn write_loader<F: Write>(
+    out: &mut SourceWriter<F>,
+    functions: &[Function],
+    struct_name: &str,
+    loader_name: &str,
+    config: &Config,
+    callbacks: Option<&dyn WriteCallbacks>,
+) {
+    write!(
+        out,
+        "bool {}({} *out, void *handle)",
+        loader_name, struct_name
+    );
+    out.push_set_spaces(2);
+    out.open_brace();
+
+    for function in functions {
+        let name = function.path().name();
+        // The struct field was written through the `ident` callback, so
+        // assign to that (possibly renamed) field; `dlsym` still looks up
+        // the real symbol name.
+        let field = callbacks
+            .and_then(|cb| cb.ident(name))
+            .unwrap_or_else(|| name.to_owned());
+        let func_ptr = func_ptr_type(function);
+
+        write!(out, "out->{} = (", field);
+        cdecl::write_type(out, &func_ptr, config, callbacks);
+        write!(out, ")dlsym(handle, \"{}\");", name);
+        out.new_line();
+
+        write!(out, "if (!out->{}) {{ return false; }}", field);
+        out.new_line();
+    }
+
+    out.write("return true;");
+    out.pop_tab();
+    out.new_line();
+    out.close_brace(false);
+}
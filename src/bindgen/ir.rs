@@ -0,0 +1,136 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The intermediate representation `cdecl.rs` (and friends) render into C.
+// This only covers the subset of the IR that the declaration writer needs;
+// the rest of cbindgen's IR (structs, enums, constants, ...) lives
+// elsewhere and isn't part of this slice of the crate.
+
+use crate::bindgen::declarationtyperesolver::DeclarationType;
+
+/// A Rust type, already resolved to the shape `cdecl.rs` expects to render.
+#[derive(Clone)]
+pub enum Type {
+    Path(Path),
+    Primitive(PrimitiveType),
+    Ptr {
+        ty: Box<Type>,
+        is_const: bool,
+        is_nullable: bool,
+        is_ref: bool,
+        /// Whether the Rust side annotated this pointer `restrict`
+        /// (N1570 §6.7.3), e.g. via `#[restrict]` on the argument.
+        is_restrict: bool,
+    },
+    /// An array, with its length and whether the pointer it decays to may be
+    /// null (`cdecl.rs` emits C99's `[static N]` array-parameter hint only
+    /// when it may not and the length is a non-zero constant).
+    Array(Box<Type>, Constant, bool),
+    FuncPtr(Box<Type>, Vec<(Option<String>, Type)>),
+}
+
+impl std::fmt::Debug for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Type")
+    }
+}
+
+/// A resolved path to a user type, e.g. a struct or enum.
+#[derive(Clone)]
+pub struct Path {
+    name: String,
+    generics: Vec<Type>,
+    ctype: Option<DeclarationType>,
+}
+
+impl Path {
+    pub fn new(name: String, generics: Vec<Type>, ctype: Option<DeclarationType>) -> Path {
+        Path {
+            name,
+            generics,
+            ctype,
+        }
+    }
+
+    pub fn export_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn generics(&self) -> &[Type] {
+        &self.generics
+    }
+
+    pub fn ctype(&self) -> Option<&DeclarationType> {
+        self.ctype.as_ref()
+    }
+}
+
+/// A C primitive type.
+#[derive(Clone)]
+pub enum PrimitiveType {
+    Void,
+    Bool,
+    Char,
+    Integer(&'static str),
+    Float(&'static str),
+}
+
+impl std::fmt::Display for PrimitiveType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PrimitiveType::Void => f.write_str("void"),
+            PrimitiveType::Bool => f.write_str("bool"),
+            PrimitiveType::Char => f.write_str("char"),
+            PrimitiveType::Integer(name) | PrimitiveType::Float(name) => f.write_str(name),
+        }
+    }
+}
+
+/// An array length, as written by the Rust author (a literal, or a named
+/// constant expression).
+#[derive(Clone)]
+pub struct Constant(String);
+
+impl Constant {
+    pub fn new(value: String) -> Constant {
+        Constant(value)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct FunctionArgument {
+    pub name: Option<String>,
+    pub ty: Type,
+}
+
+pub struct FunctionPath(String);
+
+impl FunctionPath {
+    pub fn new(name: String) -> FunctionPath {
+        FunctionPath(name)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+pub struct Function {
+    path: FunctionPath,
+    pub args: Vec<FunctionArgument>,
+    pub ret: Type,
+}
+
+impl Function {
+    pub fn new(path: FunctionPath, args: Vec<FunctionArgument>, ret: Type) -> Function {
+        Function { path, args, ret }
+    }
+
+    pub fn path(&self) -> &FunctionPath {
+        &self.path
+    }
+}
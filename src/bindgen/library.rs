@@ -0,0 +1,75 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+// The orchestrator that turns a set of `Function`s into a finished header.
+// This is the one place in this slice of the crate that actually drives
+// `cdecl`, `dynamic_loading`, and `postprocessing` end to end; the rest of
+// cbindgen's real `library.rs` additionally walks structs, enums, constants,
+// etc., which aren't part of this trimmed-down tree. Those other `ir/*.rs`
+// writers (and their own `write_func`/`write_field`/`write_type` call sites)
+// aren't part of this slice either, so this is the only place a
+// `config.write_callbacks` can be threaded through from here.
+
+use std::io::Write;
+
+use crate::bindgen::cdecl;
+use crate::bindgen::config::Config;
+use crate::bindgen::dynamic_loading;
+use crate::bindgen::ir::Function;
+use crate::bindgen::postprocessing;
+use crate::bindgen::writer::SourceWriter;
+
+/// Renders `functions` as a C header into `out`, honoring
+/// `config.write_callbacks`, `config.export.dynamic_loading`, and
+/// `config.export.postprocess`.
+pub fn generate_header<F: Write>(out: &mut F, functions: &[Function], config: &Config) {
+    let mut buffer = Vec::new();
+    let callbacks = config.write_callbacks.as_deref();
+
+    {
+        let mut writer = SourceWriter::new(&mut buffer);
+
+        // With dynamic loading, the function-pointer struct and loader take
+        // the place of the plain `extern` prototypes.
+        let prototypes: &[Function] = if config.export.dynamic_loading.is_some() {
+            &[]
+        } else {
+            functions
+        };
+        for (i, function) in prototypes.iter().enumerate() {
+            if i != 0 {
+                writer.new_line();
+            }
+            cdecl::write_func(&mut writer, function, false, config, callbacks);
+            writer.write(";");
+            writer.new_line();
+        }
+
+        if let Some(dl) = &config.export.dynamic_loading {
+            dynamic_loading::write_api_struct(
+                &mut writer,
+                functions,
+                &dl.struct_name,
+                config,
+                callbacks,
+            );
+            writer.new_line();
+            dynamic_loading::write_loader(
+                &mut writer,
+                functions,
+                &dl.struct_name,
+                &dl.loader_name,
+                config,
+                callbacks,
+            );
+            writer.new_line();
+        }
+    }
+
+    let generated = String::from_utf8(buffer).expect("generated header is not valid UTF-8");
+    let generated = postprocessing::run(&generated, &config.export.postprocess);
+
+    out.write_all(generated.as_bytes())
+        .expect("failed to write generated header");
+}
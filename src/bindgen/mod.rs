@@ -0,0 +1,20 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+mod cdecl;
+mod config;
+mod declarationtyperesolver;
+mod dynamic_loading;
+mod ir;
+mod library;
+mod postprocessing;
+mod writer;
+
+pub use self::cdecl::WriteCallbacks;
+pub use self::config::{Config, DynamicLoadingConfig, ExportConfig, Language, PtrConfig, Style};
+pub use self::ir::{
+    Constant, Function, FunctionArgument, FunctionPath, Path, PrimitiveType, Type,
+};
+pub use self::library::generate_header;
+pub use self::postprocessing::PostProcessingPass;
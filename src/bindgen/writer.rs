@@ -0,0 +1,97 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+use std::io::Write;
+
+use crate::bindgen::config::Config;
+use crate::bindgen::ir::Type;
+
+/// How a horizontal list of items (e.g. generic arguments) should be joined.
+pub enum ListType<'a> {
+    Join(&'a str),
+}
+
+/// A thin wrapper around a `Write` that tracks indentation and line length,
+/// so the `cdecl` writer can lay out function signatures either inline or
+/// one argument per (aligned) line.
+pub struct SourceWriter<F: Write> {
+    out: F,
+    spaces: Vec<usize>,
+    line_length: usize,
+}
+
+impl<F: Write> SourceWriter<F> {
+    pub fn new(out: F) -> SourceWriter<F> {
+        SourceWriter {
+            out,
+            spaces: Vec::new(),
+            line_length: 0,
+        }
+    }
+
+    pub fn write(&mut self, text: &str) {
+        let _ = self.out.write_all(text.as_bytes());
+        self.line_length += text.len();
+    }
+
+    /// An inherent `write_fmt` (rather than implementing `io::Write`/
+    /// `fmt::Write`) so the `write!(out, ...)` calls throughout `cdecl.rs`
+    /// don't produce a `Result` callers would have to handle: a failure to
+    /// write to an in-memory header buffer isn't something any caller here
+    /// could meaningfully recover from.
+    pub fn write_fmt(&mut self, args: fmt::Arguments) {
+        self.write(&args.to_string());
+    }
+
+    pub fn new_line(&mut self) {
+        let _ = self.out.write_all(b"\n");
+        self.line_length = 0;
+        if let Some(indent) = self.spaces.last() {
+            self.write(&" ".repeat(*indent));
+        }
+    }
+
+    pub fn line_length_for_align(&self) -> usize {
+        self.line_length
+    }
+
+    pub fn push_set_spaces(&mut self, spaces: usize) {
+        self.spaces.push(spaces);
+    }
+
+    pub fn pop_tab(&mut self) {
+        self.spaces.pop();
+    }
+
+    pub fn open_brace(&mut self) {
+        self.write(" {");
+        self.new_line();
+    }
+
+    /// Closes a brace opened with `open_brace`. When `semicolon` is `true`,
+    /// writes a trailing `;` right after the `}` (for e.g. `typedef struct`).
+    pub fn close_brace(&mut self, semicolon: bool) {
+        self.write("}");
+        if semicolon {
+            self.write(";");
+        }
+    }
+
+    pub fn write_horizontal_source_list(
+        &mut self,
+        items: &[Type],
+        list_type: ListType,
+        config: &Config,
+        callbacks: Option<&dyn super::cdecl::WriteCallbacks>,
+    ) {
+        let ListType::Join(sep) = list_type;
+        for (i, item) in items.iter().enumerate() {
+            if i != 0 {
+                self.write(sep);
+            }
+            super::cdecl::write_type(self, item, config, callbacks);
+        }
+    }
+}
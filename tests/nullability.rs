@@ -0,0 +1,4 @@
+#[no_mangle]
+pub extern "C" fn nullability_get(maybe: Option<*const i32>, definitely: *const i32) -> i32 {
+    unsafe { *definitely + maybe.map_or(0, |p| *p) }
+}
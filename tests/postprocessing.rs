@@ -0,0 +1,9 @@
+#[no_mangle]
+pub extern "C" fn pp_uses_helper() -> i32 {
+    pp_helper()
+}
+
+#[no_mangle]
+pub extern "C" fn pp_helper() -> i32 {
+    1
+}
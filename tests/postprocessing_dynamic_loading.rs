@@ -0,0 +1,9 @@
+#[no_mangle]
+pub extern "C" fn dl_add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[no_mangle]
+pub extern "C" fn dl_greet(name: *const i8) {
+    let _ = name;
+}
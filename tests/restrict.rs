@@ -0,0 +1,9 @@
+#[no_mangle]
+pub extern "C" fn restrict_copy(#[restrict] dst: *mut i32, src: *const i32, buf: [i32; 4]) {
+    let _ = buf;
+    unsafe {
+        for i in 0..4 {
+            *dst.add(i) = src.add(i).read();
+        }
+    }
+}